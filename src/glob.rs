@@ -0,0 +1,50 @@
+// Перевод простого шаблона glob (например "*.rs") в регулярное выражение.
+// Правила перевода такие же, как в `Regex::from_glob` из внешнего проекта MorOS:
+// весь шаблон заключается в `^...$`, `*` превращается в `.*`, `?` — в `.`,
+// а буквальная точка экранируется как `\.`.
+//
+// Шаблон принимается уже очищенным от ведущего `/` (привязка к корню) и
+// завершающего `/` (только для директорий) — этим занимается вызывающий код
+// в `walk.rs`, т.к. эти признаки влияют не на саму регулярку, а на то, с чем
+// её сравнивать.
+pub fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    for ch in glob.chars() {
+        match ch {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '.' => regex.push_str("\\."),
+            '(' | ')' | '[' | ']' | '{' | '}' | '+' | '^' | '$' | '|' | '\\' => {
+                regex.push('\\');
+                regex.push(ch);
+            }
+            _ => regex.push(ch),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    #[test]
+    fn translates_star_and_question_mark() {
+        let re = Regex::new(&glob_to_regex("*.rs")).unwrap();
+        assert!(re.is_match("main.rs"));
+        assert!(!re.is_match("main.rs.bak"));
+
+        let re = Regex::new(&glob_to_regex("a?c")).unwrap();
+        assert!(re.is_match("abc"));
+        assert!(!re.is_match("ac"));
+    }
+
+    #[test]
+    fn escapes_regex_metacharacters() {
+        let re = Regex::new(&glob_to_regex("a.b")).unwrap();
+        assert!(re.is_match("a.b"));
+        assert!(!re.is_match("aXb"));
+    }
+}
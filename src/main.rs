@@ -19,143 +19,571 @@
 
 // -n — «номер строки», напечатать номер строки
 
+mod glob;
+mod printer;
+mod walk;
 
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
+use clap::{Parser, ValueEnum};
+use printer::{print_json_begin, print_json_end, print_json_line, print_match_line};
 use regex::Regex;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::ops::Range;
+use std::path::Path;
+use termcolor::{ColorChoice, StandardStream};
+
+/// Режим раскраски вывода, как у `--color` в ripgrep
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ColorMode {
+    Never,
+    Always,
+    Auto,
+}
+
+impl From<ColorMode> for ColorChoice {
+    fn from(mode: ColorMode) -> Self {
+        match mode {
+            ColorMode::Never => ColorChoice::Never,
+            ColorMode::Always => ColorChoice::Always,
+            // termcolor сам не проверяет, является ли stdout терминалом
+            ColorMode::Auto if io::stdout().is_terminal() => ColorChoice::Auto,
+            ColorMode::Auto => ColorChoice::Never,
+        }
+    }
+}
+
+/// Консольный фильтр по аналогии с утилитой grep.
+// -h здесь занят под --no-filename (как в самом grep), поэтому автоматический
+// короткий флаг справки отключается, а --help остаётся доступен в длинной форме
+#[derive(Parser, Debug)]
+#[command(
+    name = "grep",
+    about = "Консольный фильтр по аналогии с утилитой grep",
+    version,
+    disable_help_flag = true
+)]
+struct Cli {
+    /// Показать справку
+    #[arg(long = "help", action = clap::ArgAction::Help)]
+    help: Option<bool>,
+
+    /// Шаблон для поиска
+    pattern: String,
+
+    /// Файлы для поиска. "-" (или отсутствие файлов) означает чтение из stdin
+    files: Vec<String>,
+
+    /// -A — «после», печатать +N строк после совпадения
+    #[arg(short = 'A', long = "after-context", default_value_t = 0)]
+    after: usize,
+
+    /// -B — «до», печатать +N строк перед совпадением
+    #[arg(short = 'B', long = "before-context", default_value_t = 0)]
+    before: usize,
+
+    /// -C — «контекст» (A+B), печатать ±N строк вокруг совпадения
+    #[arg(short = 'C', long = "context", default_value_t = 0)]
+    context: usize,
+
+    /// -c — «счет», вывести только количество совпадающих строк
+    #[arg(short = 'c', long = "count")]
+    count: bool,
+
+    /// -i — «игнорировать регистр»
+    #[arg(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+
+    /// -v — «инвертировать», вывести строки, не совпадающие с шаблоном
+    #[arg(short = 'v', long = "invert-match")]
+    invert: bool,
+
+    /// -F — «фиксированное», точное совпадение со строкой, а не по шаблону
+    #[arg(short = 'F', long = "fixed-strings")]
+    fixed: bool,
+
+    /// -w — «целое слово», совпадение только по границам слова
+    #[arg(short = 'w', long = "word-regexp")]
+    word: bool,
+
+    /// -n — «номер строки»
+    #[arg(short = 'n', long = "line-number")]
+    number: bool,
+
+    /// -b — байтовое смещение начала совпадающей строки относительно начала файла
+    #[arg(short = 'b', long = "byte-offset")]
+    byte_offset: bool,
+
+    /// -h — не выводить имя файла перед совпадением, даже если файлов несколько
+    #[arg(short = 'h', long = "no-filename")]
+    no_filename: bool,
+
+    /// -r — рекурсивно искать по директориям
+    #[arg(short = 'r', long = "recursive")]
+    recursive: bool,
+
+    /// Не пропускать скрытые файлы и директории (начинающиеся с точки) при рекурсивном обходе
+    #[arg(long = "hidden")]
+    hidden: bool,
+
+    /// При рекурсивном обходе искать только в файлах, чьё имя соответствует
+    /// данному glob-шаблону (например "*.rs"); явно указанные файлы не фильтруются
+    #[arg(long = "glob")]
+    glob: Option<String>,
+
+    /// Выводить результаты построчно в виде JSON-объектов (begin/match/end),
+    /// как `--json` у ripgrep — удобно для потребления редакторами и скриптами
+    #[arg(long = "json")]
+    json: bool,
+
+    /// Подсвечивать совпадения в терминале: never/always/auto (по умолчанию —
+    /// только когда stdout является терминалом)
+    #[arg(long = "color", value_enum, default_value = "auto")]
+    color: ColorMode,
+}
 
 // Структура для хранения опций командной строки
-#[derive(Default)]
-struct Options {
-    after: usize,   // Количество строк после совпадения
-    before: usize,  // Количество строк перед совпадением
-    context: usize, // Количество строк до и после совпадения (если используется)
-    count: bool,    // Флаг для подсчета совпадений
-    ignore_case: bool, // Флаг для игнорирования регистра
-    invert: bool,   // Флаг для инвертирования поиска
-    fixed: bool,    // Флаг для фиксированного (точного) поиска
-    number: bool,   // Флаг для вывода номеров строк
-}
-
-// Функция для выполнения поиска в файле
-fn grep(file_path: &str, pattern: &str, options: &Options) -> Result<(), Box<dyn std::error::Error>> {
-    // Открытие файла для чтения
-    let file = File::open(file_path)?;
-    let reader = io::BufReader::new(file);
-
-    let mut matching_lines = Vec::new();
-    let regex = if options.fixed {
-        None // Если фиксированный поиск, регулярное выражение не создается
-    } else {
-        // Создание регулярного выражения с учетом игнорирования регистра
-        let flags = if options.ignore_case { "(?i)" } else { "" };
-        Some(Regex::new(&format!("{}{}", flags, regex::escape(pattern)))?) // Создание регулярного выражения
-    };
+pub struct Options {
+    after: usize,             // Количество строк после совпадения
+    before: usize,            // Количество строк перед совпадением
+    count: bool,              // Флаг для подсчета совпадений
+    ignore_case: bool,        // Флаг для игнорирования регистра
+    invert: bool,             // Флаг для инвертирования поиска
+    fixed: bool,              // Флаг для фиксированного (точного) поиска
+    word: bool,               // Флаг для поиска только по границам слова
+    pub(crate) number: bool,  // Флаг для вывода номеров строк
+    pub(crate) show_filename: bool, // Выводить ли префикс с именем файла перед строкой
+    json: bool,               // Флаг для структурированного JSON-вывода
+    pub(crate) byte_offset: bool, // Флаг для вывода байтового смещения строки
+}
 
-    // Чтение файла построчно
-    for (line_number, line) in reader.lines().enumerate() {
-        let line = line?; // Чтение строки
-        // Проверка на совпадение
-        let matched = if options.fixed {
-            line.contains(pattern) // Если фиксированный поиск, проверяем на вхождение
-        } else {
-            regex.as_ref().map_or(false, |r| r.is_match(&line)) // Используем регулярное выражение
-        };
+impl Options {
+    fn new(cli: &Cli, show_filename: bool) -> Self {
+        // -C задаёт одновременно before и after, если они не указаны отдельно
+        let before = if cli.before > 0 { cli.before } else { cli.context };
+        let after = if cli.after > 0 { cli.after } else { cli.context };
 
-        // Добавление совпадений в вектор в зависимости от инверсии
-        if options.invert {
-            if !matched {
-                matching_lines.push((line_number, line)); // Если не совпадает и инверсия активна
-            }
-        } else {
-            if matched {
-                matching_lines.push((line_number, line)); // Если совпадает
-            }
+        Options {
+            after,
+            before,
+            count: cli.count,
+            ignore_case: cli.ignore_case,
+            invert: cli.invert,
+            fixed: cli.fixed,
+            word: cli.word,
+            number: cli.number,
+            show_filename,
+            json: cli.json,
+            byte_offset: cli.byte_offset,
         }
     }
+}
 
-    // Вывод результатов
-    if options.count {
-        println!("{}", matching_lines.len()); // Вывод количества совпадений
+// Открывает файл по пути или, если путь "-", возвращает stdin
+fn open_source(path: &str) -> io::Result<Box<dyn BufRead>> {
+    if path == "-" {
+        Ok(Box::new(BufReader::new(io::stdin())))
     } else {
-        for (line_number, line) in matching_lines.iter() {
-            if options.number {
-                println!("{}: {}", line_number + 1, line); // Вывод номера строки и содержимого
-            } else {
-                println!("{}", line); // Вывод только содержимого
-            }
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+// Компилирует регулярное выражение для поиска. В режиме -F (`fixed`) без -w
+// и без -i поиск ведётся простым подстроковым сравнением и регулярное
+// выражение не нужно; во всех остальных случаях `pattern` компилируется как
+// настоящее регулярное выражение (а не экранируется, как раньше), -i
+// добавляет флаг `(?i)` (в том числе для -F — иначе -F -i молча работал бы
+// с учётом регистра), а -w оборачивает шаблон в `\b(?:...)\b`, чтобы
+// совпадали только целые слова.
+fn build_regex(pattern: &str, options: &Options) -> Result<Option<Regex>, Box<dyn std::error::Error>> {
+    if options.fixed && !options.word && !options.ignore_case {
+        return Ok(None);
+    }
+
+    let body = if options.fixed {
+        regex::escape(pattern)
+    } else {
+        pattern.to_string()
+    };
+    let body = if options.word {
+        format!(r"\b(?:{})\b", body)
+    } else {
+        body
+    };
+    let flags = if options.ignore_case { "(?i)" } else { "" };
+
+    Regex::new(&format!("{}{}", flags, body))
+        .map(Some)
+        .map_err(|e| format!("неверное регулярное выражение {:?}: {}", pattern, e).into())
+}
+
+// Функция для выполнения поиска в одном источнике (файл или stdin).
+// В отличие от прежней реализации, которая сначала собирала все совпадения
+// в Vec, поиск потоковый (как `search_stream` в ripgrep): строки печатаются
+// по мере обнаружения совпадений, а для строк "до" хранится только кольцевой
+// буфер фиксированного размера `options.before`. Это ограничивает память
+// O(before) независимо от количества совпадений.
+fn grep(
+    stdout: &mut StandardStream,
+    label: &str,
+    mut reader: Box<dyn BufRead>,
+    pattern: &str,
+    options: &Options,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let regex = build_regex(pattern, options)?;
+
+    // Кольцевой буфер последних `before` прочитанных строк вместе с их номером
+    // и байтовым смещением начала. Без предварительного резервирования:
+    // `before` приходит прямо от пользователя (-B/-C) без верхней границы, и
+    // `with_capacity(options.before)` на огромном значении попытался бы сразу
+    // выделить столько же памяти и уронил бы процесс. Буфер и так не растёт
+    // дальше `options.before` благодаря вытеснению ниже.
+    let mut before_buffer: VecDeque<(usize, usize, String)> = VecDeque::new();
+    // Сколько ещё строк после совпадения нужно напечатать как контекст
+    let mut after_context_remaining = 0usize;
+    // Номер последней напечатанной строки — нужен, чтобы не дублировать
+    // строки при пересечении соседних контекстных областей и чтобы знать,
+    // когда печатать разделитель "--" между несмежными группами
+    let mut last_printed: Option<usize> = None;
+    let mut match_count = 0usize;
+
+    if options.json {
+        print_json_begin(label);
+    }
+
+    // Читаем построчно вручную, а не через `reader.lines().enumerate()`,
+    // чтобы отслеживать байтовое смещение начала каждой строки (нужно для -b) —
+    // `lines()` отдаёт только индекс строки, но не её позицию в байтах.
+    let mut raw_line = Vec::new();
+    let mut byte_offset = 0usize;
+    let mut line_number = 0usize;
+
+    loop {
+        raw_line.clear();
+        let bytes_read = reader.read_until(b'\n', &mut raw_line)?;
+        if bytes_read == 0 {
+            break; // Конец файла
+        }
+        let line_start_offset = byte_offset;
+        byte_offset += bytes_read;
+
+        strip_line_terminator(&mut raw_line);
+        let line = String::from_utf8(std::mem::take(&mut raw_line))?;
+
+        // Диапазоны всех вхождений шаблона в строке — используются и для
+        // определения совпадения, и для подсветки/JSON-submatches
+        let match_ranges = find_match_ranges(&line, pattern, &regex);
+        let matched = !match_ranges.is_empty();
+        let is_match = matched != options.invert; // Инверсия меняет, что считается совпадением
+
+        if is_match {
+            match_count += 1;
+        }
+
+        if !options.count {
+            if is_match {
+                let before_start = context_before_start(line_number, options.before);
 
-            // Печать контекста
-            if options.after > 0 || options.before > 0 {
-                let start = line_number.saturating_sub(options.before); // Начальная строка
-                let end = line_number + 1 + options.after; // Конечная строка
-
-                // Вывод контекстных строк
-                for context_line in &matching_lines[start..end] {
-                    if context_line.0 != *line_number { // Проверка, чтобы не выводить само совпадение
-                        if options.number {
-                            println!("{}: {}", context_line.0 + 1, context_line.1); // Вывод номера строки
-                        } else {
-                            println!("{}", context_line.1); // Вывод только содержимого
-                        }
+                // Разделитель между несмежными группами совпадений (как у grep);
+                // в JSON-режиме разделитель не нужен — группы видны по номерам строк,
+                // а без -A/-B/-C групп контекста нет и разделитель не печатается
+                let has_context = options.before > 0 || options.after > 0;
+                if !options.json && has_context && needs_separator(before_start, last_printed) {
+                    writeln!(stdout, "--")?;
+                }
+
+                // Печать накопленных строк "до", ещё не выведенных ранее
+                for (n, offset, buffered) in before_buffer.iter() {
+                    if should_print_buffered(*n, before_start, last_printed) {
+                        let context_line = Line { number: *n, byte_offset: *offset, text: buffered };
+                        print_event(stdout, label, context_line, false, &[], options)?;
+                        last_printed = Some(*n);
                     }
                 }
+
+                let matched_line = Line { number: line_number, byte_offset: line_start_offset, text: &line };
+                print_event(stdout, label, matched_line, true, &match_ranges, options)?;
+                last_printed = Some(line_number);
+                after_context_remaining = options.after;
+            } else if after_context_remaining > 0 {
+                let context_line = Line { number: line_number, byte_offset: line_start_offset, text: &line };
+                print_event(stdout, label, context_line, false, &[], options)?;
+                last_printed = Some(line_number);
+                after_context_remaining -= 1;
+            }
+
+            if options.before > 0 {
+                if before_buffer.len() == options.before {
+                    before_buffer.pop_front();
+                }
+                before_buffer.push_back((line_number, line_start_offset, line));
             }
         }
+
+        line_number += 1;
+    }
+
+    if options.json {
+        print_json_end(label, match_count);
+    } else if options.count {
+        if options.show_filename {
+            println!("{}:{}", label, match_count);
+        } else {
+            println!("{}", match_count); // Вывод количества совпадений
+        }
     }
 
     Ok(())
 }
 
+// Отбрасывает завершающий перевод строки, как это делает `BufRead::lines`:
+// один `\n`, которому может предшествовать один `\r` — и не более того. Наивное
+// "отбрасывать все \r и \n с конца" портит последнюю строку файла без
+// завершающего \n (например `b"abc\r"` превратилось бы в "abc" вместо "abc\r")
+// и задвоенный `\r` (`b"foo\r\r\n"` превратилось бы в "foo" вместо "foo\r").
+fn strip_line_terminator(raw: &mut Vec<u8>) {
+    if raw.last() == Some(&b'\n') {
+        raw.pop();
+        if raw.last() == Some(&b'\r') {
+            raw.pop();
+        }
+    }
+}
 
-fn main() {
-    let args: Vec<String> = env::args().collect(); // Получение аргументов командной строки
-    if args.len() < 3 {
-        eprintln!("Usage: {} <pattern> <file> [options]", args[0]); // Проверка на количество аргументов
-        return; // Выход из программы
+// Начало диапазона "до" для совпадения на данной строке: не может уйти ниже 0
+fn context_before_start(line_number: usize, before: usize) -> usize {
+    line_number.saturating_sub(before)
+}
+
+// Нужен ли перед новой группой контекста разделитель "--": да, если она не
+// примыкает к уже напечатанным строкам (то есть между ними есть пропуск)
+fn needs_separator(before_start: usize, last_printed: Option<usize>) -> bool {
+    match last_printed {
+        Some(last) => before_start > last + 1,
+        None => false,
     }
+}
 
-    let pattern = &args[1]; // Шаблон для поиска
-    let file_path = &args[2]; // Путь к файлу
+// Нужно ли напечатать буферизованную строку "до": да, если она попадает в
+// диапазон контекста и ещё не была напечатана ранее (как часть предыдущей
+// группы совпадений) — иначе соседние контекстные области задублировали бы её
+fn should_print_buffered(n: usize, before_start: usize, last_printed: Option<usize>) -> bool {
+    n >= before_start && last_printed.is_none_or(|last| n > last)
+}
 
-    let mut options = Options::default(); // Инициализация опций
+// Находит байтовые диапазоны всех вхождений шаблона в строке — используются
+// как для определения совпадения, так и для подсветки и JSON-submatches.
+// Если регулярное выражение не построено (-F без -w и без -i), ищем все
+// вхождения подстроки напрямую.
+fn find_match_ranges(line: &str, pattern: &str, regex: &Option<Regex>) -> Vec<Range<usize>> {
+    match regex {
+        Some(r) => r.find_iter(line).map(|m| m.start()..m.end()).collect(),
+        None => line
+            .match_indices(pattern)
+            .map(|(start, matched)| start..start + matched.len())
+            .collect(),
+    }
+}
 
-    // Обработка аргументов опций
-    for arg in &args[3..] {
-        match arg.as_str() {
-            arg if arg.starts_with("-A") => {
-                options.after = arg[2..].parse().unwrap_or(0); // Установка количества строк после совпадения
-            }
-            arg if arg.starts_with("-B") => {
-                options.before = arg[2..].parse().unwrap_or(0); // Установка количества строк перед совпадением
-            }
-            arg if arg.starts_with("-C") => {
-                options.context = arg[2..].parse().unwrap_or(0); // Установка количества контекстных строк
-            }
-            "-c" => {
-                options.count = true; // Установка флага подсчета
-            }
-            "-i" => {
-                options.ignore_case = true; // Установка флага игнорирования регистра
-            }
-            "-v" => {
-                options.invert = true; // Установка флага инверсии поиска
-            }
-            "-F" => {
-                options.fixed = true; // Установка флага фиксированного поиска
-            }
-            "-n" => {
-                options.number = true; // Установка флага для вывода номеров строк
+// Прочитанная строка вместе с её позицией во входном потоке
+struct Line<'a> {
+    number: usize,
+    byte_offset: usize,
+    text: &'a str,
+}
+
+// Печатает одну строку результата (совпадение или контекст) в текущем формате вывода
+fn print_event(
+    stdout: &mut StandardStream,
+    label: &str,
+    line: Line,
+    is_match: bool,
+    ranges: &[Range<usize>],
+    options: &Options,
+) -> io::Result<()> {
+    if options.json {
+        let submatches: Vec<(usize, usize)> = ranges.iter().map(|r| (r.start, r.end)).collect();
+        print_json_line(label, line.number, line.text, is_match, &submatches);
+        Ok(())
+    } else {
+        let byte_offset = options.byte_offset.then_some(line.byte_offset);
+        print_match_line(stdout, label, Some(line.number), byte_offset, line.text, ranges, options)
+    }
+}
+
+// Разворачивает список аргументов-путей: директории в режиме -r разворачиваются
+// в список файлов внутри них (с учетом .gitignore/.ignore, скрытых файлов и glob),
+// обычные файлы и "-" остаются как есть.
+fn resolve_files(
+    paths: &[String],
+    recursive: bool,
+    hidden: bool,
+    glob: Option<&Regex>,
+) -> Vec<String> {
+    let mut resolved = Vec::new();
+    for path in paths {
+        if path != "-" && recursive && Path::new(path).is_dir() {
+            for file in walk::collect_files(Path::new(path), hidden, glob) {
+                resolved.push(file.to_string_lossy().into_owned());
             }
-            _ => {
-                eprintln!("Неизвестная опция: {}", arg); // Сообщение об ошибке для неизвестной опции
+        } else {
+            resolved.push(path.clone());
+        }
+    }
+    resolved
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let glob_regex = match cli.glob.as_deref().map(|g| Regex::new(&glob::glob_to_regex(g))) {
+        Some(Ok(re)) => Some(re),
+        Some(Err(e)) => {
+            eprintln!("Неверный glob-шаблон {:?}: {}", cli.glob, e);
+            return;
+        }
+        None => None,
+    };
+
+    // Если файлы не указаны, читаем stdin (поведение grep по умолчанию)
+    let files: Vec<String> = if cli.files.is_empty() {
+        vec!["-".to_string()]
+    } else {
+        resolve_files(&cli.files, cli.recursive, cli.hidden, glob_regex.as_ref())
+    };
+
+    let show_filename = files.len() > 1 && !cli.no_filename;
+    let options = Options::new(&cli, show_filename);
+    let mut stdout = StandardStream::stdout(cli.color.into());
+
+    for path in &files {
+        let reader = match open_source(path) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                continue;
             }
+        };
+
+        let label = if path == "-" { "(standard input)" } else { path };
+        if let Err(e) = grep(&mut stdout, label, reader, &cli.pattern, &options) {
+            eprintln!("{}: {}", label, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn before_start_clamps_at_zero() {
+        assert_eq!(context_before_start(0, 3), 0);
+        assert_eq!(context_before_start(5, 3), 2);
+    }
+
+    #[test]
+    fn separator_only_between_non_adjacent_groups() {
+        // Новая группа примыкает к уже напечатанным строкам — без разделителя
+        assert!(!needs_separator(3, Some(2)));
+        // Между группами есть пропуск — нужен разделитель
+        assert!(needs_separator(4, Some(2)));
+        // Самая первая группа — разделителя не требуется
+        assert!(!needs_separator(0, None));
+    }
+
+    #[test]
+    fn buffered_lines_are_not_reprinted_once_already_shown() {
+        // ещё не показанная строка в пределах контекста — печатаем
+        assert!(should_print_buffered(3, 2, Some(2)));
+        // уже напечатана как часть предыдущей группы — не дублируем
+        assert!(!should_print_buffered(2, 2, Some(2)));
+        // вне диапазона "до" текущего совпадения — не печатаем
+        assert!(!should_print_buffered(1, 2, Some(0)));
+    }
+
+    fn test_options(fixed: bool, word: bool, ignore_case: bool) -> Options {
+        Options {
+            after: 0,
+            before: 0,
+            count: false,
+            ignore_case,
+            invert: false,
+            fixed,
+            word,
+            number: false,
+            show_filename: false,
+            json: false,
+            byte_offset: false,
         }
     }
 
-    // Выполнение функции grep и обработка ошибок
-    if let Err(e) = grep(file_path, pattern, &options) {
-        eprintln!("Error: {}", e); // Вывод ошибки, если она возникла
+    #[test]
+    fn strip_line_terminator_removes_single_lf() {
+        let mut raw = b"abc\n".to_vec();
+        strip_line_terminator(&mut raw);
+        assert_eq!(raw, b"abc");
+    }
+
+    #[test]
+    fn strip_line_terminator_removes_single_crlf() {
+        let mut raw = b"abc\r\n".to_vec();
+        strip_line_terminator(&mut raw);
+        assert_eq!(raw, b"abc");
+    }
+
+    #[test]
+    fn strip_line_terminator_keeps_trailing_cr_without_following_lf() {
+        // Последняя строка файла без завершающего \n — \r не является частью
+        // перевода строки и не должен отбрасываться
+        let mut raw = b"abc\r".to_vec();
+        strip_line_terminator(&mut raw);
+        assert_eq!(raw, b"abc\r");
+    }
+
+    #[test]
+    fn strip_line_terminator_does_not_over_strip_doubled_cr() {
+        // Только один \r перед \n относится к переводу строки, второй — часть содержимого
+        let mut raw = b"foo\r\r\n".to_vec();
+        strip_line_terminator(&mut raw);
+        assert_eq!(raw, b"foo\r");
+    }
+
+    #[test]
+    fn strip_line_terminator_leaves_line_without_terminator_untouched() {
+        let mut raw = b"no newline".to_vec();
+        strip_line_terminator(&mut raw);
+        assert_eq!(raw, b"no newline");
+    }
+
+    #[test]
+    fn fixed_without_word_or_case_skips_regex() {
+        let options = test_options(true, false, false);
+        assert!(build_regex("a.b", &options).unwrap().is_none());
+    }
+
+    #[test]
+    fn fixed_with_ignore_case_still_builds_case_insensitive_regex() {
+        let options = test_options(true, false, true);
+        let re = build_regex("A.B", &options).unwrap().unwrap();
+        assert!(re.is_match("xa.bx"));
+        // -F экранирует точку, она не должна значить "любой символ"
+        assert!(!re.is_match("xaXbx"));
+    }
+
+    #[test]
+    fn word_wraps_pattern_in_boundaries() {
+        let options = test_options(false, true, false);
+        let re = build_regex("cat", &options).unwrap().unwrap();
+        assert!(re.is_match("a cat sat"));
+        assert!(!re.is_match("concatenate"));
+    }
+
+    #[test]
+    fn fixed_with_word_escapes_pattern_before_wrapping() {
+        let options = test_options(true, true, false);
+        let re = build_regex("a.b", &options).unwrap().unwrap();
+        assert!(re.is_match("x a.b x"));
+        assert!(!re.is_match("xaXbx"));
     }
 }
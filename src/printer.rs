@@ -0,0 +1,158 @@
+// Вывод результатов поиска: обычный текстовый формат (с опциональной
+// подсветкой совпадений через termcolor) и структурированный JSON
+// (по одному объекту на событие), как у `--json` в ripgrep.
+
+use crate::Options;
+use std::io::{self, Write};
+use std::ops::Range;
+use termcolor::{Color, ColorSpec, StandardStream, WriteColor};
+
+// Печатает одну строку вывода с учетом префикса имени файла, байтового
+// смещения, номера строки и подсветки совпадений. `ranges` — байтовые
+// диапазоны совпадений в `line`; для строк контекста передаётся пустой
+// список (такие строки не подсвечиваются, даже если случайно содержат
+// шаблон — как и в grep/ripgrep). `byte_offset`, если задан, печатается
+// перед номером строки (см. -b/--byte-offset).
+pub fn print_match_line(
+    stdout: &mut StandardStream,
+    label: &str,
+    line_number: Option<usize>,
+    byte_offset: Option<usize>,
+    line: &str,
+    ranges: &[Range<usize>],
+    options: &Options,
+) -> io::Result<()> {
+    if options.show_filename {
+        stdout.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)))?;
+        write!(stdout, "{}", label)?;
+        stdout.reset()?;
+        write!(stdout, ":")?;
+    }
+    if let Some(offset) = byte_offset {
+        write!(stdout, "{}:", offset)?;
+    }
+    if options.number {
+        if let Some(n) = line_number {
+            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+            write!(stdout, "{}", n + 1)?;
+            stdout.reset()?;
+            write!(stdout, ":")?;
+        }
+    }
+
+    let mut pos = 0;
+    for range in ranges {
+        write!(stdout, "{}", &line[pos..range.start])?;
+        stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
+        write!(stdout, "{}", &line[range.start..range.end])?;
+        stdout.reset()?;
+        pos = range.end;
+    }
+    writeln!(stdout, "{}", &line[pos..])?;
+
+    Ok(())
+}
+
+// Экранирует строку для безопасной вставки в JSON
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// Запись "begin" — печатается один раз перед обработкой файла
+pub fn print_json_begin(label: &str) {
+    println!("{{\"type\":\"begin\",\"data\":{{\"path\":\"{}\"}}}}", json_escape(label));
+}
+
+// Запись "match"/"context" — одна на каждую напечатанную строку.
+// `submatches` — байтовые диапазоны вхождений шаблона внутри строки,
+// пустые для строк контекста (они не содержат самого совпадения).
+pub fn print_json_line(
+    label: &str,
+    line_number: usize,
+    line: &str,
+    is_match: bool,
+    submatches: &[(usize, usize)],
+) {
+    println!("{}", format_json_line(label, line_number, line, is_match, submatches));
+}
+
+// Собирает JSON-объект для одной строки "match"/"context" — вынесено из
+// `print_json_line` отдельной чистой функцией, чтобы формирование JSON можно
+// было протестировать без перехвата stdout.
+fn format_json_line(
+    label: &str,
+    line_number: usize,
+    line: &str,
+    is_match: bool,
+    submatches: &[(usize, usize)],
+) -> String {
+    let mut json = format!(
+        "{{\"type\":\"{}\",\"data\":{{\"path\":\"{}\",\"line_number\":{},\"line\":\"{}\"",
+        if is_match { "match" } else { "context" },
+        json_escape(label),
+        line_number + 1,
+        json_escape(line),
+    );
+
+    json.push_str(",\"submatches\":[");
+    for (i, (start, end)) in submatches.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        json.push_str(&format!("{{\"start\":{},\"end\":{}}}", start, end));
+    }
+    json.push_str("]}}");
+
+    json
+}
+
+// Запись "end" — печатается один раз после обработки файла, несёт
+// итоговое количество совпадений (то же значение, что выводит -c/--count)
+pub fn print_json_end(label: &str, match_count: usize) {
+    println!(
+        "{{\"type\":\"end\",\"data\":{{\"path\":\"{}\",\"stats\":{{\"matches\":{}}}}}}}",
+        json_escape(label),
+        match_count
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_escape_handles_quotes_backslashes_and_control_chars() {
+        assert_eq!(json_escape("a\"b\\c"), "a\\\"b\\\\c");
+        assert_eq!(json_escape("line1\nline2\ttab\r"), "line1\\nline2\\ttab\\r");
+        assert_eq!(json_escape("\u{1}"), "\\u0001");
+        assert_eq!(json_escape("обычный текст"), "обычный текст");
+    }
+
+    #[test]
+    fn format_json_line_reports_match_type_and_one_indexed_line_number() {
+        let json = format_json_line("a.txt", 0, "needle here", true, &[(0, 6)]);
+        assert!(json.contains("\"type\":\"match\""));
+        assert!(json.contains("\"line_number\":1"));
+        assert!(json.contains("\"submatches\":[{\"start\":0,\"end\":6}]"));
+    }
+
+    #[test]
+    fn format_json_line_reports_context_type_with_no_submatches() {
+        let json = format_json_line("a.txt", 4, "surrounding line", false, &[]);
+        assert!(json.contains("\"type\":\"context\""));
+        assert!(json.contains("\"line_number\":5"));
+        assert!(json.contains("\"submatches\":[]"));
+    }
+}
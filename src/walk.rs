@@ -0,0 +1,186 @@
+// Рекурсивный обход директорий с учетом правил .gitignore/.ignore,
+// по аналогии с тем, как это делает ripgrep.
+
+use crate::glob::glob_to_regex;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Одно правило из .gitignore/.ignore, скомпилированное в регулярное выражение
+struct IgnoreRule {
+    regex: Regex,
+    negate: bool,   // true для правил вида "!шаблон" (исключение из игнорирования)
+    dir_only: bool, // true для правил с завершающим "/" — совпадают только с директориями
+    anchored: bool, // true для правил с ведущим "/" — сравниваются с путём
+    // относительно директории, где лежит сам .gitignore/.ignore,
+    // а не только с именем файла
+}
+
+// Читает .gitignore/.ignore в указанной директории и возвращает список правил
+fn load_ignore_rules(dir: &Path) -> Vec<IgnoreRule> {
+    let mut rules = Vec::new();
+    for name in [".gitignore", ".ignore"] {
+        let Ok(content) = fs::read_to_string(dir.join(name)) else {
+            continue;
+        };
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negate, pattern) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+
+            let anchored = pattern.starts_with('/');
+            let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+            let dir_only = pattern.ends_with('/');
+            let pattern = pattern.strip_suffix('/').unwrap_or(pattern);
+
+            if let Ok(regex) = Regex::new(&glob_to_regex(pattern)) {
+                rules.push(IgnoreRule {
+                    regex,
+                    negate,
+                    dir_only,
+                    anchored,
+                });
+            }
+        }
+    }
+    rules
+}
+
+// Проверяет, игнорируется ли запись накопленным стеком правил.
+// `rel_paths[i]` — путь записи относительно директории, в которой был загружен
+// уровень стека `stack[i]` (нужно для привязанных к корню правил вида "/foo").
+// Более глубокие (более специфичные) уровни имеют приоритет над внешними.
+fn is_ignored(name: &str, is_dir: bool, rel_paths: &[String], stack: &[Vec<IgnoreRule>]) -> bool {
+    let mut ignored = false;
+    for (level, rel_path) in stack.iter().zip(rel_paths) {
+        for rule in level {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let candidate = if rule.anchored {
+                rel_path.as_str()
+            } else {
+                name
+            };
+            if rule.regex.is_match(candidate) {
+                ignored = !rule.negate;
+            }
+        }
+    }
+    ignored
+}
+
+/// Рекурсивно собирает пути ко всем файлам внутри `root`, пропуская скрытые
+/// файлы (если `hidden` не установлен) и всё, что исключено правилами
+/// .gitignore/.ignore. Если передан `glob`, в результат попадают только файлы,
+/// чьё имя соответствует этому шаблону.
+pub fn collect_files(root: &Path, hidden: bool, glob: Option<&Regex>) -> Vec<PathBuf> {
+    let mut stack = Vec::new();
+    let mut dirs = Vec::new();
+    let mut results = Vec::new();
+    walk(root, hidden, glob, &mut stack, &mut dirs, &mut results);
+    results
+}
+
+fn walk(
+    dir: &Path,
+    hidden: bool,
+    glob: Option<&Regex>,
+    stack: &mut Vec<Vec<IgnoreRule>>,
+    dirs: &mut Vec<PathBuf>,
+    results: &mut Vec<PathBuf>,
+) {
+    stack.push(load_ignore_rules(dir));
+    dirs.push(dir.to_path_buf());
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        let mut entries: Vec<_> = entries.flatten().collect();
+        entries.sort_by_key(|e| e.file_name());
+
+        for entry in entries {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+
+            if !hidden && file_name.starts_with('.') {
+                continue;
+            }
+
+            let path = entry.path();
+            let is_dir = path.is_dir();
+            let rel_paths: Vec<String> = dirs
+                .iter()
+                .map(|d| {
+                    path.strip_prefix(d)
+                        .unwrap_or(&path)
+                        .to_string_lossy()
+                        .replace(std::path::MAIN_SEPARATOR, "/")
+                })
+                .collect();
+
+            if is_ignored(&file_name, is_dir, &rel_paths, stack) {
+                continue;
+            }
+
+            if is_dir {
+                walk(&path, hidden, glob, stack, dirs, results);
+            } else if glob.is_none_or(|re| re.is_match(&file_name)) {
+                results.push(path);
+            }
+        }
+    }
+
+    dirs.pop();
+    stack.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn trailing_slash_matches_only_directories() {
+        let dir = std::env::temp_dir().join("grep_walk_test_trailing_slash");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+        fs::write(dir.join("target/artifact.txt"), "needle\n").unwrap();
+        fs::write(dir.join("keep.txt"), "needle\n").unwrap();
+
+        let files = collect_files(&dir, false, None);
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+
+        assert!(names.contains(&"keep.txt".to_string()));
+        assert!(!names.contains(&"artifact.txt".to_string()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn leading_slash_anchors_to_ignore_file_directory() {
+        let dir = std::env::temp_dir().join("grep_walk_test_leading_slash");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join(".gitignore"), "/secret.txt\n").unwrap();
+        fs::write(dir.join("secret.txt"), "needle\n").unwrap();
+        fs::write(dir.join("sub/secret.txt"), "needle\n").unwrap();
+
+        let files = collect_files(&dir, false, None);
+        let rel: Vec<_> = files
+            .iter()
+            .map(|p| p.strip_prefix(&dir).unwrap().to_string_lossy().to_string())
+            .collect();
+
+        assert!(!rel.contains(&"secret.txt".to_string()));
+        assert!(rel.iter().any(|p| p.ends_with("sub/secret.txt") || p.ends_with("sub\\secret.txt")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}